@@ -1,7 +1,13 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::cell::UnsafeCell;
+use std::future::Future;
+use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Weak;
-
-use ringbuf::{Consumer as RbConsumer, Producer as RbProducer, RingBuffer};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
 
 use super::{IoEvents, IoNotifier};
 use crate::events::{Event, EventFilter, Notifier, Observer, Waiter, WaiterQueueObserver};
@@ -16,13 +22,20 @@ pub struct Channel<I> {
 
 impl<I> Channel<I> {
     /// Create a new channel.
+    ///
+    /// A `capacity` of zero gives a rendezvous channel: a blocking `push`
+    /// does not return until a `pop` has accepted the exact item (and vice
+    /// versa), i.e., there is no buffering at all.
     pub fn new(capacity: usize) -> Result<Self> {
         let state = Arc::new(State::new());
+        let buffer = Arc::new(if capacity == 0 {
+            Buffer::Rendezvous(Rendezvous::new())
+        } else {
+            Buffer::Ring(Ring::with_capacity(capacity))
+        });
 
-        let rb = RingBuffer::new(capacity);
-        let (rb_producer, rb_consumer) = rb.split();
-        let producer = Producer::new(rb_producer, state.clone());
-        let consumer = Consumer::new(rb_consumer, state.clone());
+        let producer = Producer::new_from_buffer(buffer.clone(), state.clone());
+        let consumer = Consumer::new_from_buffer(buffer, state.clone());
 
         // Make event connection between the producer and consumer
         producer.notifier().register(
@@ -35,6 +48,8 @@ impl<I> Channel<I> {
             None,
             None,
         );
+        producer.set_peer_notifier(consumer.notifier().clone());
+        consumer.set_peer_notifier(producer.notifier().clone());
 
         Ok(Self { producer, consumer })
     }
@@ -53,10 +68,8 @@ impl<I> Channel<I> {
     /// in case of an `EAGAIN` or `EINTR` error. For this reason, we need a way
     /// for the caller to get back the ownership of the input item upon error.
     /// Thus, an extra argument is added to this method.
-    // TODO: implement this method in the future when pushing items individually is
-    // really needed
     pub fn push_noncopy(&self, item: I, retry: &mut Option<I>) -> Result<()> {
-        unimplemented!();
+        self.producer.push_noncopy(item, retry)
     }
 
     /// Pop an item out of the channel.
@@ -64,6 +77,31 @@ impl<I> Channel<I> {
         self.consumer.pop()
     }
 
+    /// Push an item into the channel, giving up with `ETIMEDOUT` if `timeout`
+    /// elapses before there is room for it.
+    pub fn push_timeout(&self, item: I, timeout: Duration) -> Result<()> {
+        self.producer.push_timeout(item, timeout)
+    }
+
+    /// Pop an item out of the channel, giving up with `ETIMEDOUT` if
+    /// `timeout` elapses before an item is available.
+    pub fn pop_timeout(&self, timeout: Duration) -> Result<Option<I>> {
+        self.consumer.pop_timeout(timeout)
+    }
+
+    /// Returns a `CancelToken` that can be used to abort a thread currently
+    /// blocked in `push`/`pop` (or their slice/timeout variants) on either
+    /// endpoint of this channel.
+    pub fn cancel_token(&self) -> CancelToken {
+        CancelToken {
+            state: Arc::clone(&self.producer.state),
+            producer_notifier: self.producer.notifier().clone(),
+            producer_observer: Arc::clone(&self.producer.observer),
+            consumer_notifier: self.consumer.notifier().clone(),
+            consumer_observer: Arc::clone(&self.consumer.observer),
+        }
+    }
+
     /// Turn the channel into a pair of producer and consumer.
     pub fn split(self) -> (Producer<I>, Consumer<I>) {
         let Channel { producer, consumer } = self;
@@ -81,30 +119,183 @@ impl<I: Copy> Channel<I> {
     pub fn pop_slice(&self, items: &mut [I]) -> Result<usize> {
         self.consumer.pop_slice(items)
     }
+
+    /// Push a slice of items into the channel, giving up with `ETIMEDOUT` if
+    /// `timeout` elapses before any of them can be pushed.
+    pub fn push_slice_timeout(&self, items: &[I], timeout: Duration) -> Result<usize> {
+        self.producer.push_slice_timeout(items, timeout)
+    }
+
+    /// Pop a slice of items from the channel, giving up with `ETIMEDOUT` if
+    /// `timeout` elapses before any of them can be popped.
+    pub fn pop_slice_timeout(&self, items: &mut [I], timeout: Duration) -> Result<usize> {
+        self.consumer.pop_slice_timeout(items, timeout)
+    }
+}
+
+/// Pads a value out to a cache line so that the producer's `head` and the
+/// consumer's `tail` never share a cache line, avoiding false-sharing
+/// ping-pong between cores.
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+impl<T> CachePadded<T> {
+    const fn new(val: T) -> Self {
+        Self(val)
+    }
+}
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// A lock-free single-producer/single-consumer ring buffer.
+///
+/// `head` is only ever written by the producer and `tail` is only ever
+/// written by the consumer; both are monotonically increasing counters whose
+/// low bits (via `mask`) give the slot index. The buffer is empty when
+/// `head == tail` and full when `head - tail == capacity`.
+struct Ring<I> {
+    buf: Box<[UnsafeCell<MaybeUninit<I>>]>,
+    mask: usize,
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+}
+
+// Safety: access to each slot is handed off between the producer and the
+// consumer exactly once (guarded by the `head`/`tail` acquire-release
+// protocol), so `Ring<I>` can be shared across the two threads as long as
+// `I` itself is safe to send between threads.
+unsafe impl<I: Send> Send for Ring<I> {}
+unsafe impl<I: Send> Sync for Ring<I> {}
+
+impl<I> Ring<I> {
+    /// Create a ring whose capacity is the next power of two of `capacity`
+    /// (minimum 1), as required by the `head & mask` slot indexing.
+    fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.next_power_of_two().max(1);
+
+        let mut buf = Vec::with_capacity(capacity);
+        for _ in 0..capacity {
+            buf.push(UnsafeCell::new(MaybeUninit::uninit()));
+        }
+
+        Self {
+            buf: buf.into_boxed_slice(),
+            mask: capacity - 1,
+            head: CachePadded::new(AtomicUsize::new(0)),
+            tail: CachePadded::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.mask + 1
+    }
+
+    fn slot(&self, idx: usize) -> *mut MaybeUninit<I> {
+        self.buf[idx & self.mask].get()
+    }
+}
+
+impl<I> Drop for Ring<I> {
+    fn drop(&mut self) {
+        // No concurrent access is possible while dropping, so relaxed reads
+        // of the raw counters are fine.
+        let head = *self.head.get_mut();
+        let mut tail = *self.tail.get_mut();
+        while tail != head {
+            // Safety: every slot in [tail, head) holds an initialized `I`
+            // that has not been popped yet.
+            unsafe {
+                ptr::drop_in_place((*self.slot(tail)).as_mut_ptr());
+            }
+            tail = tail.wrapping_add(1);
+        }
+    }
+}
+
+/// The storage backing a channel: either a buffered ring, or a zero-capacity
+/// rendezvous handoff.
+enum Buffer<I> {
+    Ring(Ring<I>),
+    Rendezvous(Rendezvous<I>),
+}
+
+/// A zero-capacity handoff slot used to give a `Channel::new(0)` channel
+/// rendezvous semantics: a blocking `push` parks its item here and does not
+/// return until `pop` has taken it back out; a non-blocking `push` returns
+/// once its item is staged for a consumer that is already waiting.
+struct Rendezvous<I> {
+    slot: SgxMutex<RendezvousSlot<I>>,
+}
+
+struct RendezvousSlot<I> {
+    /// The item a producer has staged, waiting for a consumer to take it.
+    item: Option<I>,
+    /// Whether a consumer is currently parked waiting for an item, which is
+    /// what lets a non-blocking `push` hand off without blocking.
+    receiver_waiting: bool,
+}
+
+impl<I> Rendezvous<I> {
+    fn new() -> Self {
+        Self {
+            slot: SgxMutex::new(RendezvousSlot {
+                item: None,
+                receiver_waiting: false,
+            }),
+        }
+    }
 }
 
 /// An endpoint is either the producer or consumer of a channel.
-pub struct EndPoint<T> {
-    inner: SgxMutex<T>,
+///
+/// Endpoints are reference-counted: cloning an endpoint (see the `Clone`
+/// impls on `Producer`/`Consumer` below) models e.g. a pipe fd that has been
+/// `dup`'d across threads, or a socketpair fanned out to a worker pool. The
+/// peer-shutdown/HUP semantics only fire once the *last* clone of a side is
+/// dropped.
+pub struct EndPoint<T>(Arc<EndPointInner<T>>);
+
+struct EndPointInner<T> {
+    inner: T,
     state: Arc<State>,
     observer: Arc<WaiterQueueObserver<IoEvents>>,
     notifier: IoNotifier,
+    // Set once, right after both endpoints of a channel exist (see
+    // `Channel::new`). Lets an endpoint reach the notifier that broadcasts
+    // the events relevant to *its own* readiness (e.g. a producer's `OUT`
+    // is broadcast by the consumer, on a pop), without the other endpoint
+    // having to know it in advance.
+    peer_notifier: SgxMutex<Option<IoNotifier>>,
     is_nonblocking: AtomicBool,
 }
 
+impl<T> Deref for EndPoint<T> {
+    type Target = EndPointInner<T>;
+
+    fn deref(&self) -> &EndPointInner<T> {
+        &self.0
+    }
+}
+
 impl<T> EndPoint<T> {
     fn new(inner: T, state: Arc<State>) -> Self {
-        let inner = SgxMutex::new(inner);
         let observer = WaiterQueueObserver::new();
         let notifier = IoNotifier::new();
         let is_nonblocking = AtomicBool::new(false);
-        Self {
+        Self(Arc::new(EndPointInner {
             inner,
             state,
             observer,
             notifier,
+            peer_notifier: SgxMutex::new(None),
             is_nonblocking,
-        }
+        }))
     }
 
     /// Returns the I/O notifier.
@@ -115,6 +306,21 @@ impl<T> EndPoint<T> {
         &self.notifier
     }
 
+    fn set_peer_notifier(&self, notifier: IoNotifier) {
+        *self.peer_notifier.lock().unwrap() = Some(notifier);
+    }
+
+    /// Returns the notifier that broadcasts the events relevant to this
+    /// endpoint's own readiness (as opposed to `notifier()`, which this
+    /// endpoint broadcasts on *for the other side's* benefit).
+    fn peer_notifier(&self) -> IoNotifier {
+        self.peer_notifier
+            .lock()
+            .unwrap()
+            .clone()
+            .expect("peer notifier is set by Channel::new before any endpoint method runs")
+    }
+
     /// Returns whether the endpoint is non-blocking.
     ///
     /// By default, a channel is blocking.
@@ -131,19 +337,35 @@ impl<T> EndPoint<T> {
             self.observer.waiter_queue().dequeue_and_wake_all();
         }
     }
+
+    /// Returns whether the channel's `CancelToken` has been cancelled.
+    fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled.load(Ordering::Acquire)
+    }
 }
 
 /// The state of a channel shared by the two endpoints of a channel.
+///
+/// `producer_count`/`consumer_count` track how many live clones exist of
+/// each side (see the `Clone`/`Drop` impls on `Producer`/`Consumer`); the
+/// shutdown flags are only set once the respective count drops to zero, or
+/// `shutdown()` is called explicitly.
 struct State {
+    producer_count: AtomicUsize,
+    consumer_count: AtomicUsize,
     is_producer_shutdown: AtomicBool,
     is_consumer_shutdown: AtomicBool,
+    is_cancelled: AtomicBool,
 }
 
 impl State {
     pub fn new() -> Self {
         Self {
+            producer_count: AtomicUsize::new(1),
+            consumer_count: AtomicUsize::new(1),
             is_producer_shutdown: AtomicBool::new(false),
             is_consumer_shutdown: AtomicBool::new(false),
+            is_cancelled: AtomicBool::new(false),
         }
     }
 
@@ -164,69 +386,427 @@ impl State {
     }
 }
 
+/// A lightweight cancellation flag shared by both endpoints of a channel.
+/// Call `cancel()` from a supervisor thread to abort a thread currently
+/// blocked in `push`/`pop` (or their slice/timeout variants) on either side,
+/// causing it to wake up and return `ECANCELED`.
+pub struct CancelToken {
+    state: Arc<State>,
+    producer_notifier: IoNotifier,
+    producer_observer: Arc<WaiterQueueObserver<IoEvents>>,
+    consumer_notifier: IoNotifier,
+    consumer_observer: Arc<WaiterQueueObserver<IoEvents>>,
+}
+
+impl CancelToken {
+    /// Returns whether `cancel()` has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled.load(Ordering::Acquire)
+    }
+
+    /// Cancel any `push`/`pop` currently blocked on either endpoint of the
+    /// channel this token was obtained from.
+    pub fn cancel(&self) {
+        self.state.is_cancelled.store(true, Ordering::Release);
+
+        self.producer_notifier.broadcast(&IoEvents::all());
+        self.producer_observer.waiter_queue().dequeue_and_wake_all();
+
+        self.consumer_notifier.broadcast(&IoEvents::all());
+        self.consumer_observer.waiter_queue().dequeue_and_wake_all();
+    }
+}
+
+/// A one-shot `Observer` that wakes a `Waker` when any event fires. This is
+/// the bridge between the notifier/broadcast machinery used by the blocking
+/// `push`/`pop` API and `std::future::Future`: a fresh instance is registered
+/// each time `poll_push`/`poll_pop` would otherwise block, and the `Arc` is
+/// held by the `Future` so that simply replacing it (on the next `poll`) or
+/// dropping it (if the `Future` is dropped mid-await) lets the old `Weak`
+/// registration go dead without any explicit unregistration.
+struct WakerObserver {
+    waker: Waker,
+}
+
+impl WakerObserver {
+    fn new(waker: Waker) -> Arc<Self> {
+        Arc::new(Self { waker })
+    }
+}
+
+impl Observer<IoEvents> for WakerObserver {
+    fn on_event(&self, _events: &IoEvents) {
+        self.waker.wake_by_ref();
+    }
+}
+
+/// Returns the time remaining until `deadline`, or `ETIMEDOUT` if it has
+/// already elapsed. Used by the blocking push/pop loops to turn an absolute
+/// deadline into the relative duration `Waiter::wait` expects.
+fn time_remaining(deadline: Option<Instant>) -> Result<Option<Duration>> {
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return Ok(None),
+    };
+
+    let now = Instant::now();
+    if now >= deadline {
+        return_errno!(ETIMEDOUT, "timed out");
+    }
+    Ok(Some(deadline - now))
+}
+
 // Just like a normal loop, except that a waiter queue (as well as a waiter)
 // is used to avoid busy loop. This macro is used in the push/pop implementation
-// below.
+// below. An optional timeout (relative `Duration`, converted to an absolute
+// deadline up front) can be passed as a third argument; omitting it waits
+// indefinitely. The first argument is the endpoint (`self`), whose
+// `CancelToken` flag is checked on every iteration.
 macro_rules! waiter_loop {
-    ($loop_body: block, $waiter_queue: expr) => {
+    ($endpoint: ident, $loop_body: block, $waiter_queue: expr) => {
+        waiter_loop!($endpoint, $loop_body, $waiter_queue, None)
+    };
+    ($endpoint: ident, $loop_body: block, $waiter_queue: expr, $timeout: expr) => {
         // Try without creating a waiter. This saves some CPU cycles if the
         // first attempt succeeds.
         {
             $loop_body
         }
 
+        let deadline = $timeout.map(|timeout: Duration| Instant::now() + timeout);
+
         // The main loop
         let waiter = Waiter::new();
         let waiter_queue = $waiter_queue;
         loop {
             waiter_queue.reset_and_enqueue(&waiter);
 
+            if $endpoint.is_cancelled() {
+                return_errno!(ECANCELED, "the operation was cancelled");
+            }
+
             {
                 $loop_body
             }
 
-            waiter.wait(None)?;
+            let remaining = time_remaining(deadline)?;
+            waiter.wait(remaining)?;
         }
     };
 }
 
+/// The producer-side cursor into the ring: the real `head` (owned by this
+/// side) plus a cached copy of the consumer's `tail`, used to avoid an
+/// atomic load on the hot path when there is already known to be space.
+///
+/// The buffer is strictly single-writer, so when a `Producer` has been
+/// cloned, `push_lock` serializes the clones' pushes against one another;
+/// only the thread holding the lock actually touches the buffer.
+struct ProducerRing<I> {
+    buffer: Arc<Buffer<I>>,
+    cached_tail: AtomicUsize,
+    push_lock: SgxMutex<()>,
+}
+
 /// Producer is the writable endpoint of a channel.
-pub type Producer<I> = EndPoint<RbProducer<I>>;
+pub type Producer<I> = EndPoint<ProducerRing<I>>;
 
 impl<I> Producer<I> {
-    pub fn push(&self, mut item: I) -> Result<()> {
-        waiter_loop!(
-            {
-                let mut rb_producer = self.inner.lock().unwrap();
-                if self.is_self_shutdown() || self.is_peer_shutdown() {
-                    return_errno!(EPIPE, "one or both endpoints have been shutdown");
+    fn new_from_buffer(buffer: Arc<Buffer<I>>, state: Arc<State>) -> Self {
+        Self::new(
+            ProducerRing {
+                buffer,
+                cached_tail: AtomicUsize::new(0),
+                push_lock: SgxMutex::new(()),
+            },
+            state,
+        )
+    }
+
+    pub fn push(&self, item: I) -> Result<()> {
+        self.push_impl(item, None).map_err(|(err, _item)| err)
+    }
+
+    /// Like `push`, but giving up with `ETIMEDOUT` if `timeout` elapses
+    /// before the item can be pushed.
+    pub fn push_timeout(&self, item: I, timeout: Duration) -> Result<()> {
+        self.push_impl(item, Some(timeout))
+            .map_err(|(err, _item)| err)
+    }
+
+    /// Push an non-copy item, handing the item back through `retry` if it
+    /// could not be pushed (on `EAGAIN`/`EINTR`), since `item` is otherwise
+    /// consumed by this call.
+    pub fn push_noncopy(&self, item: I, retry: &mut Option<I>) -> Result<()> {
+        match self.push_impl(item, None) {
+            Ok(()) => Ok(()),
+            Err((err, item)) => {
+                if err.errno() == EAGAIN || err.errno() == EINTR {
+                    *retry = Some(item);
                 }
+                Err(err)
+            }
+        }
+    }
+
+    fn push_impl(
+        &self,
+        item: I,
+        timeout: Option<Duration>,
+    ) -> std::result::Result<(), (Error, I)> {
+        match &*self.inner.buffer {
+            Buffer::Ring(ring) => self.push_ring(ring, item, timeout),
+            Buffer::Rendezvous(rv) => self.push_rendezvous(rv, item, timeout),
+        }
+    }
+
+    fn push_ring(
+        &self,
+        ring: &Ring<I>,
+        mut item: I,
+        timeout: Option<Duration>,
+    ) -> std::result::Result<(), (Error, I)> {
+        if self.is_self_shutdown() || self.is_peer_shutdown() {
+            return Err((
+                errno!(EPIPE, "one or both endpoints have been shutdown"),
+                item,
+            ));
+        }
+        item = match self.try_push_ring(ring, item) {
+            Ok(()) => {
+                self.notifier.broadcast(&IoEvents::IN);
+                return Ok(());
+            }
+            Err(item) => item,
+        };
+        if self.is_nonblocking() {
+            return Err((errno!(EAGAIN, "try again later"), item));
+        }
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let waiter = Waiter::new();
+        loop {
+            self.observer.waiter_queue().reset_and_enqueue(&waiter);
 
-                item = match rb_producer.push(item) {
-                    Ok(()) => {
-                        drop(rb_producer);
-                        self.notifier.broadcast(&IoEvents::IN);
-                        return Ok(());
+            if self.is_cancelled() {
+                return Err((errno!(ECANCELED, "the operation was cancelled"), item));
+            }
+            if self.is_self_shutdown() || self.is_peer_shutdown() {
+                return Err((
+                    errno!(EPIPE, "one or both endpoints have been shutdown"),
+                    item,
+                ));
+            }
+            item = match self.try_push_ring(ring, item) {
+                Ok(()) => {
+                    self.notifier.broadcast(&IoEvents::IN);
+                    return Ok(());
+                }
+                Err(item) => item,
+            };
+            if self.is_nonblocking() {
+                return Err((errno!(EAGAIN, "try again later"), item));
+            }
+
+            let remaining = match time_remaining(deadline) {
+                Ok(remaining) => remaining,
+                Err(e) => return Err((e, item)),
+            };
+            if let Err(e) = waiter.wait(remaining) {
+                return Err((e, item));
+            }
+        }
+    }
+
+    /// Implements the zero-capacity rendezvous handoff: stage the item in
+    /// the slot (blocking until the slot is free; a non-blocking push only
+    /// stages when a consumer is already waiting), then wait for the
+    /// consumer to actually take it out.
+    fn push_rendezvous(
+        &self,
+        rv: &Rendezvous<I>,
+        mut item: I,
+        timeout: Option<Duration>,
+    ) -> std::result::Result<(), (Error, I)> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let waiter = Waiter::new();
+
+        // Phase 1: get the item staged into the handoff slot.
+        loop {
+            if self.is_self_shutdown() || self.is_peer_shutdown() {
+                return Err((
+                    errno!(EPIPE, "one or both endpoints have been shutdown"),
+                    item,
+                ));
+            }
+            item = match self.try_stage(rv, item) {
+                Ok(()) => break,
+                Err(item) => item,
+            };
+            if self.is_nonblocking() {
+                return Err((errno!(EAGAIN, "try again later"), item));
+            }
+
+            self.observer.waiter_queue().reset_and_enqueue(&waiter);
+
+            if self.is_cancelled() {
+                return Err((errno!(ECANCELED, "the operation was cancelled"), item));
+            }
+
+            // Re-check after registering to close the race between a
+            // consumer becoming ready and us starting to wait.
+            item = match self.try_stage(rv, item) {
+                Ok(()) => break,
+                Err(item) => item,
+            };
+            if self.is_nonblocking() {
+                return Err((errno!(EAGAIN, "try again later"), item));
+            }
+
+            let remaining = match time_remaining(deadline) {
+                Ok(remaining) => remaining,
+                Err(e) => return Err((e, item)),
+            };
+            if let Err(e) = waiter.wait(remaining) {
+                return Err((e, item));
+            }
+        }
+
+        self.notifier.broadcast(&IoEvents::IN);
+
+        // A non-blocking push is done once the item is staged: it doesn't
+        // wait around for the consumer to actually take it, matching
+        // `try_push_one`'s rendezvous branch (the path `poll_push` uses).
+        if self.is_nonblocking() {
+            return Ok(());
+        }
+
+        // Phase 2: wait for the consumer to take the staged item back out.
+        loop {
+            self.observer.waiter_queue().reset_and_enqueue(&waiter);
+
+            if rv.slot.lock().unwrap().item.is_none() {
+                return Ok(());
+            }
+            // Checked after enqueueing (not before), mirroring
+            // `pop_rendezvous`: a peer shutdown racing with us has either
+            // already broadcast to our now-registered waiter, or is still to
+            // come and will wake us. Checking first would leave a window
+            // where the broadcast is lost and the item is still staged,
+            // hanging a no-timeout push forever.
+            if self.is_peer_shutdown() {
+                return match rv.slot.lock().unwrap().item.take() {
+                    Some(item) => Err((
+                        errno!(EPIPE, "the receiving end has been shutdown"),
+                        item,
+                    )),
+                    // The consumer took the item right before shutting down.
+                    None => Ok(()),
+                };
+            }
+            if self.is_cancelled() {
+                return match rv.slot.lock().unwrap().item.take() {
+                    Some(item) => Err((errno!(ECANCELED, "the operation was cancelled"), item)),
+                    None => Ok(()),
+                };
+            }
+
+            let remaining = match time_remaining(deadline) {
+                Ok(remaining) => remaining,
+                Err(e) => {
+                    return match rv.slot.lock().unwrap().item.take() {
+                        Some(item) => Err((e, item)),
+                        None => Ok(()),
                     }
-                    Err(item) => item,
+                }
+            };
+            if let Err(e) = waiter.wait(remaining) {
+                return match rv.slot.lock().unwrap().item.take() {
+                    Some(item) => Err((e, item)),
+                    None => Ok(()),
                 };
+            }
+        }
+    }
 
-                if self.is_nonblocking() {
-                    return_errno!(EAGAIN, "try again later");
+    /// Try to stage an item into the rendezvous slot without blocking. This
+    /// only succeeds when the slot is empty and (for a non-blocking
+    /// producer) a consumer is already waiting to take it.
+    fn try_stage(&self, rv: &Rendezvous<I>, item: I) -> std::result::Result<(), I> {
+        let mut slot = rv.slot.lock().unwrap();
+        if slot.item.is_none() && (slot.receiver_waiting || !self.is_nonblocking()) {
+            slot.item = Some(item);
+            Ok(())
+        } else {
+            Err(item)
+        }
+    }
+
+    /// Try to push a single item without blocking. On failure (the ring is
+    /// full, or the rendezvous slot has no waiting consumer), the item is
+    /// handed back to the caller.
+    fn try_push_one(&self, item: I) -> std::result::Result<(), I> {
+        match &*self.inner.buffer {
+            Buffer::Ring(ring) => self.try_push_ring(ring, item),
+            Buffer::Rendezvous(rv) => {
+                let mut slot = rv.slot.lock().unwrap();
+                if slot.item.is_none() && slot.receiver_waiting {
+                    slot.item = Some(item);
+                    Ok(())
+                } else {
+                    Err(item)
                 }
-            },
-            self.observer.waiter_queue()
-        );
+            }
+        }
+    }
+
+    fn try_push_ring(&self, ring: &Ring<I>, item: I) -> std::result::Result<(), I> {
+        // Serializes this single attempt against other clones of this
+        // producer; never held across a blocking wait. An uncloned producer
+        // has no one to serialize against, so skip the mutex on that common
+        // hot path rather than pay for it unconditionally.
+        let _guard = (self.state.producer_count.load(Ordering::Acquire) > 1)
+            .then(|| self.inner.push_lock.lock().unwrap());
+
+        let head = ring.head.load(Ordering::Relaxed);
+        let mut tail = self.inner.cached_tail.load(Ordering::Relaxed);
+        if head.wrapping_sub(tail) == ring.capacity() {
+            // The cached tail says the ring is full; re-read the real,
+            // possibly-advanced tail before giving up.
+            tail = ring.tail.load(Ordering::Acquire);
+            self.inner.cached_tail.store(tail, Ordering::Relaxed);
+            if head.wrapping_sub(tail) == ring.capacity() {
+                return Err(item);
+            }
+        }
+
+        // Safety: `head` is only ever advanced by us (the producer), and the
+        // consumer will not read this slot until it observes the `head`
+        // store below, so exclusive access to the slot is guaranteed.
+        unsafe {
+            (*ring.slot(head)).write(item);
+        }
+        ring.head.store(head.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    fn is_full(&self) -> bool {
+        match &*self.inner.buffer {
+            Buffer::Ring(ring) => {
+                let head = ring.head.load(Ordering::Acquire);
+                let tail = ring.tail.load(Ordering::Acquire);
+                head.wrapping_sub(tail) == ring.capacity()
+            }
+            // A rendezvous push can complete without blocking iff a
+            // consumer is already parked waiting for an item.
+            Buffer::Rendezvous(rv) => !rv.slot.lock().unwrap().receiver_waiting,
+        }
     }
 
     pub fn poll(&self) -> IoEvents {
         let mut events = IoEvents::empty();
 
-        let writable = {
-            let mut rb_producer = self.inner.lock().unwrap();
-            !rb_producer.is_full()
-        };
-        if writable {
+        if !self.is_full() {
             events |= IoEvents::OUT;
         }
 
@@ -240,12 +820,17 @@ impl<I> Producer<I> {
         events
     }
 
+    /// Shut down this side of the channel, regardless of how many clones of
+    /// this producer are still alive.
     pub fn shutdown(&self) {
-        {
-            // It is important to hold this lock while updating the state
-            let inner = self.inner.lock().unwrap();
-            self.state.set_producer_shutdown();
-        }
+        // Short-circuit the refcount so that clones dropped afterwards don't
+        // try to shut the side down a second time.
+        self.state.producer_count.store(0, Ordering::Release);
+        self.do_shutdown();
+    }
+
+    fn do_shutdown(&self) {
+        self.state.set_producer_shutdown();
 
         // Notify all consumers and other observers
         self.notifier.broadcast(&IoEvents::HUP);
@@ -262,18 +847,61 @@ impl<I> Producer<I> {
     }
 }
 
+impl<I> Clone for Producer<I> {
+    /// Clone this producer, e.g. to model a pipe write end `dup`'d across
+    /// threads. The peer only observes shutdown once every clone is dropped.
+    fn clone(&self) -> Self {
+        self.state.producer_count.fetch_add(1, Ordering::AcqRel);
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<I> Drop for Producer<I> {
+    fn drop(&mut self) {
+        if self.state.producer_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.do_shutdown();
+        }
+    }
+}
+
 impl<I: Copy> Producer<I> {
     pub fn push_slice(&self, items: &[I]) -> Result<usize> {
+        self.push_slice_impl(items, None)
+    }
+
+    /// Like `push_slice`, but giving up with `ETIMEDOUT` if `timeout`
+    /// elapses before any item can be pushed.
+    pub fn push_slice_timeout(&self, items: &[I], timeout: Duration) -> Result<usize> {
+        self.push_slice_impl(items, Some(timeout))
+    }
+
+    fn push_slice_impl(&self, items: &[I], timeout: Option<Duration>) -> Result<usize> {
+        // A rendezvous channel hands off exactly one item at a time via a
+        // single-slot announce/wake protocol (see `try_stage`); `try_push_one`
+        // doesn't participate in that protocol at all for a multi-item slice,
+        // so a `push_slice` against a rendezvous channel could block forever
+        // even with a consumer actively waiting. Reject it outright instead.
+        if matches!(&*self.inner.buffer, Buffer::Rendezvous(_)) {
+            return_errno!(
+                EINVAL,
+                "push_slice is not supported on a zero-capacity (rendezvous) channel"
+            );
+        }
         waiter_loop!(
+            self,
             {
-                let mut rb_producer = self.inner.lock().unwrap();
                 if self.is_self_shutdown() || self.is_peer_shutdown() {
                     return_errno!(EPIPE, "one or both endpoints have been shutdown");
                 }
 
-                let count = rb_producer.push_slice(items);
+                let mut count = 0;
+                while count < items.len() {
+                    match self.try_push_one(items[count]) {
+                        Ok(()) => count += 1,
+                        Err(_) => break,
+                    }
+                }
                 if count > 0 {
-                    drop(rb_producer);
                     self.notifier.broadcast(&IoEvents::IN);
                     return Ok(count);
                 }
@@ -282,25 +910,253 @@ impl<I: Copy> Producer<I> {
                     return_errno!(EAGAIN, "try again later");
                 }
             },
-            self.observer.waiter_queue()
+            self.observer.waiter_queue(),
+            timeout
         );
     }
 }
 
+impl<I> Producer<I> {
+    /// Attempt to push the item staged in `item` without blocking the
+    /// calling thread. On success `item` is left `None` and this resolves
+    /// with `Poll::Ready(Ok(()))`; if the channel isn't ready, `item` is left
+    /// in place for the next call and `waker_observer` is (re-)registered
+    /// with a fresh `WakerObserver` bound to `cx`'s waker before returning
+    /// `Poll::Pending`, so a later peer operation wakes the task.
+    ///
+    /// Used by `PushFuture`, which owns `item` and `waker_observer` across
+    /// calls; `push`/`push_timeout` are the blocking equivalents.
+    pub fn poll_push(
+        &self,
+        item: &mut Option<I>,
+        waker_observer: &mut Option<Arc<WakerObserver>>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<()>> {
+        if self.is_self_shutdown() || self.is_peer_shutdown() {
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(
+                EPIPE,
+                "one or both endpoints have been shutdown"
+            )));
+        }
+        if self.is_cancelled() {
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(ECANCELED, "the operation was cancelled")));
+        }
+
+        let staged = item.take().expect("poll_push called with no item staged");
+        match self.try_push_one(staged) {
+            Ok(()) => {
+                self.notifier.broadcast(&IoEvents::IN);
+                *waker_observer = None;
+                return Poll::Ready(Ok(()));
+            }
+            Err(staged) => *item = Some(staged),
+        }
+
+        let observer = WakerObserver::new(cx.waker().clone());
+        let weak_observer = Arc::downgrade(&observer) as Weak<dyn Observer<_>>;
+        self.notifier.register(weak_observer.clone(), None, None);
+        self.peer_notifier().register(weak_observer, None, None);
+        *waker_observer = Some(observer);
+
+        // Re-poll after registering the waker to close the lost-wakeup race
+        // between a peer pop completing and the registration landing.
+        let staged = item.take().unwrap();
+        match self.try_push_one(staged) {
+            Ok(()) => {
+                self.notifier.broadcast(&IoEvents::IN);
+                *waker_observer = None;
+                Poll::Ready(Ok(()))
+            }
+            Err(staged) => {
+                *item = Some(staged);
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Returns a `Future` that resolves once `item` has been pushed, for
+    /// driving this channel from an async executor instead of blocking the
+    /// calling thread.
+    pub fn send(&self, item: I) -> PushFuture<'_, I> {
+        PushFuture {
+            producer: self,
+            item: Some(item),
+            waker_observer: None,
+        }
+    }
+}
+
+/// A `Future` returned by `Producer::send`, resolving once the item has been
+/// pushed or the channel errors out.
+pub struct PushFuture<'a, I> {
+    producer: &'a Producer<I>,
+    item: Option<I>,
+    waker_observer: Option<Arc<WakerObserver>>,
+}
+
+impl<'a, I> Future for PushFuture<'a, I> {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.producer
+            .poll_push(&mut this.item, &mut this.waker_observer, cx)
+    }
+}
+
+impl<I: Copy> Producer<I> {
+    /// Slice counterpart of `poll_push`: attempt to push as many of `items`
+    /// as fit without blocking, resolving with the count pushed (at least
+    /// one, as with `push_slice`) or registering for a wakeup.
+    pub fn poll_push_slice(
+        &self,
+        items: &[I],
+        waker_observer: &mut Option<Arc<WakerObserver>>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<usize>> {
+        // See `push_slice_impl`: the rendezvous protocol only hands off one
+        // item at a time.
+        if matches!(&*self.inner.buffer, Buffer::Rendezvous(_)) {
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(
+                EINVAL,
+                "push_slice is not supported on a zero-capacity (rendezvous) channel"
+            )));
+        }
+        if self.is_self_shutdown() || self.is_peer_shutdown() {
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(
+                EPIPE,
+                "one or both endpoints have been shutdown"
+            )));
+        }
+        if self.is_cancelled() {
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(ECANCELED, "the operation was cancelled")));
+        }
+
+        let mut count = 0;
+        while count < items.len() {
+            match self.try_push_one(items[count]) {
+                Ok(()) => count += 1,
+                Err(_) => break,
+            }
+        }
+        if count > 0 {
+            self.notifier.broadcast(&IoEvents::IN);
+            *waker_observer = None;
+            return Poll::Ready(Ok(count));
+        }
+
+        let observer = WakerObserver::new(cx.waker().clone());
+        let weak_observer = Arc::downgrade(&observer) as Weak<dyn Observer<_>>;
+        self.notifier.register(weak_observer.clone(), None, None);
+        self.peer_notifier().register(weak_observer, None, None);
+        *waker_observer = Some(observer);
+
+        // Re-poll after registering the waker to close the lost-wakeup race
+        // between a peer pop completing and the registration landing.
+        let mut count = 0;
+        while count < items.len() {
+            match self.try_push_one(items[count]) {
+                Ok(()) => count += 1,
+                Err(_) => break,
+            }
+        }
+        if count > 0 {
+            self.notifier.broadcast(&IoEvents::IN);
+            *waker_observer = None;
+            return Poll::Ready(Ok(count));
+        }
+
+        Poll::Pending
+    }
+
+    /// Returns a `Future` that resolves once at least one item of `items`
+    /// has been pushed.
+    pub fn send_slice<'a>(&'a self, items: &'a [I]) -> PushSliceFuture<'a, I> {
+        PushSliceFuture {
+            producer: self,
+            items,
+            waker_observer: None,
+        }
+    }
+}
+
+/// A `Future` returned by `Producer::send_slice`, resolving once at least one
+/// item has been pushed or the channel errors out.
+pub struct PushSliceFuture<'a, I> {
+    producer: &'a Producer<I>,
+    items: &'a [I],
+    waker_observer: Option<Arc<WakerObserver>>,
+}
+
+impl<'a, I: Copy> Future for PushSliceFuture<'a, I> {
+    type Output = Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.producer
+            .poll_push_slice(this.items, &mut this.waker_observer, cx)
+    }
+}
+
+/// The consumer-side cursor into the ring: the real `tail` (owned by this
+/// side) plus a cached copy of the producer's `head`, used to avoid an
+/// atomic load on the hot path when there is already known to be data.
+///
+/// The buffer is strictly single-reader, so when a `Consumer` has been
+/// cloned, `pop_lock` serializes the clones' pops against one another; only
+/// the thread holding the lock actually touches the buffer.
+struct ConsumerRing<I> {
+    buffer: Arc<Buffer<I>>,
+    cached_head: AtomicUsize,
+    pop_lock: SgxMutex<()>,
+}
+
 /// Consumer is the readable endpoint of a channel.
-pub type Consumer<I> = EndPoint<RbConsumer<I>>;
+pub type Consumer<I> = EndPoint<ConsumerRing<I>>;
 
 impl<I> Consumer<I> {
+    fn new_from_buffer(buffer: Arc<Buffer<I>>, state: Arc<State>) -> Self {
+        Self::new(
+            ConsumerRing {
+                buffer,
+                cached_head: AtomicUsize::new(0),
+                pop_lock: SgxMutex::new(()),
+            },
+            state,
+        )
+    }
+
     pub fn pop(&self) -> Result<Option<I>> {
+        self.pop_impl(None)
+    }
+
+    /// Like `pop`, but giving up with `ETIMEDOUT` if `timeout` elapses
+    /// before an item is available.
+    pub fn pop_timeout(&self, timeout: Duration) -> Result<Option<I>> {
+        self.pop_impl(Some(timeout))
+    }
+
+    fn pop_impl(&self, timeout: Option<Duration>) -> Result<Option<I>> {
+        match &*self.inner.buffer {
+            Buffer::Ring(ring) => self.pop_ring(ring, timeout),
+            Buffer::Rendezvous(rv) => self.pop_rendezvous(rv, timeout),
+        }
+    }
+
+    fn pop_ring(&self, ring: &Ring<I>, timeout: Option<Duration>) -> Result<Option<I>> {
         waiter_loop!(
+            self,
             {
-                let mut rb_consumer = self.inner.lock().unwrap();
                 if self.is_self_shutdown() {
                     return_errno!(EPIPE, "this endpoint has been shutdown");
                 }
 
-                if let Some(item) = rb_consumer.pop() {
-                    drop(rb_consumer);
+                if let Some(item) = self.try_pop_ring(ring) {
                     self.notifier.broadcast(&IoEvents::OUT);
                     return Ok(Some(item));
                 }
@@ -312,18 +1168,124 @@ impl<I> Consumer<I> {
                     return_errno!(EAGAIN, "try again later");
                 }
             },
-            self.observer.waiter_queue()
+            self.observer.waiter_queue(),
+            timeout
         );
     }
 
+    /// A consumer's `pop` on a rendezvous channel announces itself as a
+    /// waiting receiver (so a non-blocking peer `push` can hand off to it)
+    /// and waits for a producer to stage an item.
+    fn pop_rendezvous(&self, rv: &Rendezvous<I>, timeout: Option<Duration>) -> Result<Option<I>> {
+        if let Some(item) = self.try_take_rendezvous(rv) {
+            return Ok(Some(item));
+        }
+        if self.is_self_shutdown() {
+            return_errno!(EPIPE, "this endpoint has been shutdown");
+        }
+        if self.is_peer_shutdown() {
+            return Ok(None);
+        }
+        if self.is_nonblocking() {
+            return_errno!(EAGAIN, "try again later");
+        }
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let waiter = Waiter::new();
+        let result = loop {
+            rv.slot.lock().unwrap().receiver_waiting = true;
+            self.observer.waiter_queue().reset_and_enqueue(&waiter);
+
+            if let Some(item) = self.try_take_rendezvous(rv) {
+                break Ok(Some(item));
+            }
+            if self.is_self_shutdown() {
+                break Err(errno!(EPIPE, "this endpoint has been shutdown"));
+            }
+            if self.is_peer_shutdown() {
+                break Ok(None);
+            }
+            if self.is_cancelled() {
+                break Err(errno!(ECANCELED, "the operation was cancelled"));
+            }
+
+            let remaining = match time_remaining(deadline) {
+                Ok(remaining) => remaining,
+                Err(e) => break Err(e),
+            };
+            if let Err(e) = waiter.wait(remaining) {
+                break Err(e);
+            }
+        };
+
+        // We are no longer a waiting receiver, regardless of how we exited.
+        rv.slot.lock().unwrap().receiver_waiting = false;
+        result
+    }
+
+    /// Try to take the staged item out of the rendezvous slot without
+    /// blocking, waking the producer that is waiting for the handoff to
+    /// complete.
+    fn try_take_rendezvous(&self, rv: &Rendezvous<I>) -> Option<I> {
+        let mut slot = rv.slot.lock().unwrap();
+        let item = slot.item.take()?;
+        slot.receiver_waiting = false;
+        drop(slot);
+        self.notifier.broadcast(&IoEvents::OUT);
+        Some(item)
+    }
+
+    /// Try to pop a single item without blocking.
+    fn try_pop_one(&self) -> Option<I> {
+        match &*self.inner.buffer {
+            Buffer::Ring(ring) => self.try_pop_ring(ring),
+            Buffer::Rendezvous(rv) => self.try_take_rendezvous(rv),
+        }
+    }
+
+    fn try_pop_ring(&self, ring: &Ring<I>) -> Option<I> {
+        // Serializes this single attempt against other clones of this
+        // consumer; never held across a blocking wait. An uncloned consumer
+        // has no one to serialize against, so skip the mutex on that common
+        // hot path rather than pay for it unconditionally.
+        let _guard = (self.state.consumer_count.load(Ordering::Acquire) > 1)
+            .then(|| self.inner.pop_lock.lock().unwrap());
+
+        let tail = ring.tail.load(Ordering::Relaxed);
+        let mut head = self.inner.cached_head.load(Ordering::Relaxed);
+        if tail == head {
+            // The cached head says the ring is empty; re-read the real,
+            // possibly-advanced head before giving up.
+            head = ring.head.load(Ordering::Acquire);
+            self.inner.cached_head.store(head, Ordering::Relaxed);
+            if tail == head {
+                return None;
+            }
+        }
+
+        // Safety: `tail` is only ever advanced by us (the consumer), and the
+        // producer will not reuse this slot until it observes the `tail`
+        // store below, so exclusive access to the slot is guaranteed.
+        let item = unsafe { (*ring.slot(tail)).assume_init_read() };
+        ring.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Some(item)
+    }
+
+    fn is_empty(&self) -> bool {
+        match &*self.inner.buffer {
+            Buffer::Ring(ring) => {
+                let head = ring.head.load(Ordering::Acquire);
+                let tail = ring.tail.load(Ordering::Acquire);
+                head == tail
+            }
+            Buffer::Rendezvous(rv) => rv.slot.lock().unwrap().item.is_none(),
+        }
+    }
+
     pub fn poll(&self) -> IoEvents {
         let mut events = IoEvents::empty();
 
-        let readable = {
-            let mut rb_consumer = self.inner.lock().unwrap();
-            !rb_consumer.is_empty()
-        };
-        if readable {
+        if !self.is_empty() {
             events |= IoEvents::IN;
         }
 
@@ -337,12 +1299,17 @@ impl<I> Consumer<I> {
         events
     }
 
+    /// Shut down this side of the channel, regardless of how many clones of
+    /// this consumer are still alive.
     pub fn shutdown(&self) {
-        {
-            // It is important to hold this lock while updating the state
-            let inner = self.inner.lock().unwrap();
-            self.state.set_consumer_shutdown();
-        }
+        // Short-circuit the refcount so that clones dropped afterwards don't
+        // try to shut the side down a second time.
+        self.state.consumer_count.store(0, Ordering::Release);
+        self.do_shutdown();
+    }
+
+    fn do_shutdown(&self) {
+        self.state.set_consumer_shutdown();
 
         // Notify all producers and other observers
         self.notifier.broadcast(&IoEvents::RDHUP);
@@ -359,21 +1326,65 @@ impl<I> Consumer<I> {
     }
 }
 
+impl<I> Clone for Consumer<I> {
+    /// Clone this consumer, e.g. to model a pipe read end `dup`'d across
+    /// threads. The peer only observes shutdown once every clone is dropped.
+    fn clone(&self) -> Self {
+        self.state.consumer_count.fetch_add(1, Ordering::AcqRel);
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<I> Drop for Consumer<I> {
+    fn drop(&mut self) {
+        if self.state.consumer_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.do_shutdown();
+        }
+    }
+}
+
 impl<I: Copy> Consumer<I> {
     pub fn pop_slice(&self, items: &mut [I]) -> Result<usize> {
+        self.pop_slice_impl(items, None)
+    }
+
+    /// Like `pop_slice`, but giving up with `ETIMEDOUT` if `timeout` elapses
+    /// before any item can be popped.
+    pub fn pop_slice_timeout(&self, items: &mut [I], timeout: Duration) -> Result<usize> {
+        self.pop_slice_impl(items, Some(timeout))
+    }
+
+    fn pop_slice_impl(&self, items: &mut [I], timeout: Option<Duration>) -> Result<usize> {
+        // See `push_slice_impl`: a rendezvous channel's single-slot
+        // announce/wake protocol doesn't extend to popping several items at
+        // once, so reject it outright rather than risk a silent hang.
+        if matches!(&*self.inner.buffer, Buffer::Rendezvous(_)) {
+            return_errno!(
+                EINVAL,
+                "pop_slice is not supported on a zero-capacity (rendezvous) channel"
+            );
+        }
         waiter_loop!(
+            self,
             {
-                let mut rb_consumer = self.inner.lock().unwrap();
                 if self.is_self_shutdown() {
                     return_errno!(EPIPE, "this endpoint has been shutdown");
                 }
 
-                let count = rb_consumer.pop_slice(items);
+                let mut count = 0;
+                while count < items.len() {
+                    match self.try_pop_one() {
+                        Some(item) => {
+                            items[count] = item;
+                            count += 1;
+                        }
+                        None => break,
+                    }
+                }
                 if count > 0 {
-                    drop(rb_consumer);
                     self.notifier.broadcast(&IoEvents::OUT);
                     return Ok(count);
-                };
+                }
 
                 if self.is_peer_shutdown() {
                     return Ok(0);
@@ -382,7 +1393,570 @@ impl<I: Copy> Consumer<I> {
                     return_errno!(EAGAIN, "try again later");
                 }
             },
-            self.observer.waiter_queue()
+            self.observer.waiter_queue(),
+            timeout
         );
     }
 }
+
+impl<I> Consumer<I> {
+    /// Attempt to pop an item without blocking the calling thread. On a
+    /// rendezvous channel this also announces (and, if it doesn't resolve
+    /// immediately, keeps announcing) this consumer as a waiting receiver, so
+    /// a non-blocking peer `push` can hand off to it, mirroring
+    /// `pop_rendezvous`.
+    ///
+    /// On success or EOF this resolves immediately; otherwise
+    /// `waker_observer` is (re-)registered with a fresh `WakerObserver` bound
+    /// to `cx`'s waker before returning `Poll::Pending`, so a later peer
+    /// operation wakes the task.
+    ///
+    /// Used by `PopFuture`, which owns `waker_observer` across calls;
+    /// `pop`/`pop_timeout` are the blocking equivalents.
+    pub fn poll_pop(
+        &self,
+        waker_observer: &mut Option<Arc<WakerObserver>>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<I>>> {
+        self.mark_receiver_waiting(true);
+
+        if self.is_self_shutdown() {
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(EPIPE, "this endpoint has been shutdown")));
+        }
+        if let Some(item) = self.try_pop_one() {
+            self.notifier.broadcast(&IoEvents::OUT);
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Ok(Some(item)));
+        }
+        if self.is_peer_shutdown() {
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Ok(None));
+        }
+        if self.is_cancelled() {
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(ECANCELED, "the operation was cancelled")));
+        }
+        let observer = WakerObserver::new(cx.waker().clone());
+        let weak_observer = Arc::downgrade(&observer) as Weak<dyn Observer<_>>;
+        self.notifier.register(weak_observer.clone(), None, None);
+        self.peer_notifier().register(weak_observer, None, None);
+        *waker_observer = Some(observer);
+
+        // Re-poll after registering the waker to close the lost-wakeup race
+        // between a peer push completing and the registration landing.
+        if let Some(item) = self.try_pop_one() {
+            self.notifier.broadcast(&IoEvents::OUT);
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Ok(Some(item)));
+        }
+        if self.is_peer_shutdown() {
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Ok(None));
+        }
+
+        Poll::Pending
+    }
+
+    /// Returns a `Future` that resolves once an item has been popped (or
+    /// `None` on EOF), for driving this channel from an async executor
+    /// instead of blocking the calling thread.
+    pub fn recv(&self) -> PopFuture<'_, I> {
+        PopFuture {
+            consumer: self,
+            waker_observer: None,
+        }
+    }
+
+    fn mark_receiver_waiting(&self, waiting: bool) {
+        if let Buffer::Rendezvous(rv) = &*self.inner.buffer {
+            rv.slot.lock().unwrap().receiver_waiting = waiting;
+        }
+    }
+}
+
+/// A `Future` returned by `Consumer::recv`, resolving once an item has been
+/// popped (or `None` on EOF) or the channel errors out.
+pub struct PopFuture<'a, I> {
+    consumer: &'a Consumer<I>,
+    waker_observer: Option<Arc<WakerObserver>>,
+}
+
+impl<'a, I> Future for PopFuture<'a, I> {
+    type Output = Result<Option<I>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.consumer.poll_pop(&mut this.waker_observer, cx)
+    }
+}
+
+impl<'a, I> Drop for PopFuture<'a, I> {
+    fn drop(&mut self) {
+        // If this future is dropped while still `Pending` (e.g. a `select!`
+        // cancelled it), `poll_pop` never got to clear the waiting-receiver
+        // flag itself; clear it here, mirroring `pop_rendezvous`'s cleanup
+        // after its blocking loop. A no-op if the future already resolved.
+        self.consumer.mark_receiver_waiting(false);
+    }
+}
+
+impl<I: Copy> Consumer<I> {
+    /// Slice counterpart of `poll_pop`: attempt to fill as much of `items` as
+    /// is available without blocking, resolving with the count popped (at
+    /// least one, or zero on EOF, as with `pop_slice`) or registering for a
+    /// wakeup.
+    pub fn poll_pop_slice(
+        &self,
+        items: &mut [I],
+        waker_observer: &mut Option<Arc<WakerObserver>>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<usize>> {
+        // See `pop_slice_impl`: the rendezvous protocol only hands off one
+        // item at a time.
+        if matches!(&*self.inner.buffer, Buffer::Rendezvous(_)) {
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(
+                EINVAL,
+                "pop_slice is not supported on a zero-capacity (rendezvous) channel"
+            )));
+        }
+
+        self.mark_receiver_waiting(true);
+
+        if self.is_self_shutdown() {
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(EPIPE, "this endpoint has been shutdown")));
+        }
+
+        let mut count = 0;
+        while count < items.len() {
+            match self.try_pop_one() {
+                Some(item) => {
+                    items[count] = item;
+                    count += 1;
+                }
+                None => break,
+            }
+        }
+        if count > 0 {
+            self.notifier.broadcast(&IoEvents::OUT);
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Ok(count));
+        }
+        if self.is_peer_shutdown() {
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Ok(0));
+        }
+        if self.is_cancelled() {
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Err(errno!(ECANCELED, "the operation was cancelled")));
+        }
+        let observer = WakerObserver::new(cx.waker().clone());
+        let weak_observer = Arc::downgrade(&observer) as Weak<dyn Observer<_>>;
+        self.notifier.register(weak_observer.clone(), None, None);
+        self.peer_notifier().register(weak_observer, None, None);
+        *waker_observer = Some(observer);
+
+        // Re-poll after registering the waker to close the lost-wakeup race
+        // between a peer push completing and the registration landing.
+        let mut count = 0;
+        while count < items.len() {
+            match self.try_pop_one() {
+                Some(item) => {
+                    items[count] = item;
+                    count += 1;
+                }
+                None => break,
+            }
+        }
+        if count > 0 {
+            self.notifier.broadcast(&IoEvents::OUT);
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Ok(count));
+        }
+        if self.is_peer_shutdown() {
+            self.mark_receiver_waiting(false);
+            *waker_observer = None;
+            return Poll::Ready(Ok(0));
+        }
+
+        Poll::Pending
+    }
+
+    /// Returns a `Future` that resolves once `items` has been filled with at
+    /// least one popped item (or zero on EOF).
+    pub fn recv_slice<'a>(&'a self, items: &'a mut [I]) -> PopSliceFuture<'a, I> {
+        PopSliceFuture {
+            consumer: self,
+            items,
+            waker_observer: None,
+        }
+    }
+}
+
+/// A `Future` returned by `Consumer::recv_slice`, resolving once at least one
+/// item has been popped (or zero on EOF) or the channel errors out.
+pub struct PopSliceFuture<'a, I> {
+    consumer: &'a Consumer<I>,
+    items: &'a mut [I],
+    waker_observer: Option<Arc<WakerObserver>>,
+}
+
+impl<'a, I: Copy> Future for PopSliceFuture<'a, I> {
+    type Output = Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.consumer
+            .poll_pop_slice(this.items, &mut this.waker_observer, cx)
+    }
+}
+
+impl<'a, I> Drop for PopSliceFuture<'a, I> {
+    fn drop(&mut self) {
+        // See `PopFuture`'s `Drop` impl: clears a waiting-receiver flag left
+        // set by a `Pending` poll that never got a chance to resolve.
+        self.consumer.mark_receiver_waiting(false);
+    }
+}
+
+/// An endpoint that can be polled for its current I/O readiness and that can
+/// notify an `Observer` when that readiness changes. `Select` is built on
+/// top of this to block on several endpoints at once.
+pub trait Pollable {
+    /// Returns the endpoint's current I/O events.
+    fn poll(&self) -> IoEvents;
+
+    /// Returns the I/O notifier this endpoint itself broadcasts on (carries,
+    /// e.g., this side's own shutdown).
+    fn notifier(&self) -> &IoNotifier;
+
+    /// Returns the I/O notifier that broadcasts the events relevant to this
+    /// endpoint's own readiness but raised by the *other* side (e.g. a
+    /// producer's `OUT`/`RDHUP`, which are broadcast by the consumer).
+    fn peer_notifier(&self) -> IoNotifier;
+}
+
+impl<I> Pollable for Producer<I> {
+    fn poll(&self) -> IoEvents {
+        self.poll()
+    }
+
+    fn notifier(&self) -> &IoNotifier {
+        self.notifier()
+    }
+
+    fn peer_notifier(&self) -> IoNotifier {
+        EndPoint::peer_notifier(self)
+    }
+}
+
+impl<I> Pollable for Consumer<I> {
+    fn poll(&self) -> IoEvents {
+        self.poll()
+    }
+
+    fn notifier(&self) -> &IoNotifier {
+        self.notifier()
+    }
+
+    fn peer_notifier(&self) -> IoNotifier {
+        EndPoint::peer_notifier(self)
+    }
+}
+
+/// Blocks a single thread on the I/O readiness of several `Pollable`
+/// endpoints at once, e.g. to implement `poll`/`select`/`epoll_wait` over a
+/// mix of pipes and unix sockets without spinning.
+///
+/// A single shared `WaiterQueueObserver` is registered on every endpoint's
+/// notifiers for the lifetime of the `Select`, so `ready()` can be called
+/// repeatedly (as an event loop would) without re-registering each time; the
+/// registration is torn down when the `Select` is dropped.
+pub struct Select<'a, T: Pollable> {
+    endpoints: Vec<(&'a T, IoEvents)>,
+    observer: Arc<WaiterQueueObserver<IoEvents>>,
+}
+
+impl<'a, T: Pollable> Select<'a, T> {
+    /// Create a `Select` over `endpoints`, each paired with the `IoEvents`
+    /// mask the caller is interested in.
+    pub fn new(endpoints: Vec<(&'a T, IoEvents)>) -> Self {
+        let observer = WaiterQueueObserver::new();
+        for (endpoint, _mask) in &endpoints {
+            let weak_observer = Arc::downgrade(&observer) as Weak<dyn Observer<_>>;
+            // `notifier()` carries this endpoint's own shutdown; `peer_notifier()`
+            // carries the events the other side raises on its behalf (e.g. a
+            // producer's `OUT`). Both are needed to track `poll()` accurately.
+            endpoint
+                .notifier()
+                .register(weak_observer.clone(), None, None);
+            endpoint.peer_notifier().register(weak_observer, None, None);
+        }
+        Self { endpoints, observer }
+    }
+
+    fn poll_once(&self) -> Vec<usize> {
+        self.endpoints
+            .iter()
+            .enumerate()
+            .filter(|(_idx, (endpoint, mask))| endpoint.poll().intersects(*mask))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Returns the indices (into the slice passed to `new`) of the endpoints
+    /// that are currently ready, blocking (unless `timeout` elapses) until
+    /// at least one is.
+    pub fn ready(&self, timeout: Option<Duration>) -> Result<Vec<usize>> {
+        let ready = self.poll_once();
+        if !ready.is_empty() {
+            return Ok(ready);
+        }
+
+        let waiter = Waiter::new();
+        loop {
+            self.observer.waiter_queue().reset_and_enqueue(&waiter);
+
+            // Re-poll after registering to close the race between an
+            // endpoint becoming ready and us starting to wait.
+            let ready = self.poll_once();
+            if !ready.is_empty() {
+                return Ok(ready);
+            }
+
+            waiter.wait(timeout)?;
+        }
+    }
+}
+
+impl<'a, T: Pollable> Drop for Select<'a, T> {
+    fn drop(&mut self) {
+        let weak_observer = Arc::downgrade(&self.observer) as Weak<dyn Observer<_>>;
+        for (endpoint, _mask) in &self.endpoints {
+            endpoint.notifier().unregister(&weak_observer);
+            endpoint.peer_notifier().unregister(&weak_observer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::{RawWaker, RawWakerVTable};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    /// A `Waker` that does nothing when woken, for driving a `Future` by
+    /// hand in a synchronous test (no real executor needed).
+    fn noop_waker() -> Waker {
+        fn no_op(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker {
+            raw_waker()
+        }
+        fn raw_waker() -> RawWaker {
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, no_op, no_op, no_op);
+            RawWaker::new(ptr::null(), &VTABLE)
+        }
+        unsafe { Waker::from_raw(raw_waker()) }
+    }
+
+    #[test]
+    fn ring_push_pop_fifo() {
+        let channel = Channel::<i32>::new(4).unwrap();
+        channel.push(1).unwrap();
+        channel.push(2).unwrap();
+        channel.push(3).unwrap();
+        assert_eq!(channel.pop().unwrap(), Some(1));
+        assert_eq!(channel.pop().unwrap(), Some(2));
+        assert_eq!(channel.pop().unwrap(), Some(3));
+    }
+
+    #[test]
+    fn ring_full_capacity_rounds_up_to_power_of_two() {
+        // `Ring::with_capacity` rounds a requested capacity of 3 up to 4, so
+        // 4 items should fit before the 5th is rejected.
+        let channel = Channel::<i32>::new(3).unwrap();
+        for item in 0..4 {
+            channel.push(item).unwrap();
+        }
+        let (producer, _consumer) = channel.split();
+        producer.set_nonblocking(true);
+        let err = producer.push(4).unwrap_err();
+        assert_eq!(err.errno(), EAGAIN);
+    }
+
+    #[test]
+    fn shutdown_is_deferred_until_last_clone_dropped() {
+        let channel = Channel::<i32>::new(1).unwrap();
+        let (producer, consumer) = channel.split();
+        let producer_clone = producer.clone();
+
+        drop(producer);
+        // One clone is still alive, so the consumer shouldn't see HUP yet.
+        assert!(!consumer.poll().contains(IoEvents::RDHUP));
+
+        drop(producer_clone);
+        assert!(consumer.poll().contains(IoEvents::RDHUP));
+        assert_eq!(consumer.pop().unwrap(), None);
+    }
+
+    #[test]
+    fn rendezvous_push_waits_for_matching_pop() {
+        let channel = Arc::new(Channel::<i32>::new(0).unwrap());
+        let popped = {
+            let channel = channel.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                channel.pop().unwrap()
+            })
+        };
+
+        // With no buffering, this blocks until the spawned thread pops it.
+        channel.push(42).unwrap();
+        assert_eq!(popped.join().unwrap(), Some(42));
+    }
+
+    #[test]
+    fn rendezvous_push_unblocks_on_shutdown_while_staged() {
+        let channel = Channel::<i32>::new(0).unwrap();
+        let (producer, consumer) = channel.split();
+        let producer = Arc::new(producer);
+
+        let pusher = {
+            let producer = producer.clone();
+            thread::spawn(move || producer.push(1))
+        };
+
+        // Give the pusher time to stage its item and start waiting for a
+        // pop, then shut the consumer down without ever popping it.
+        thread::sleep(Duration::from_millis(50));
+        drop(consumer);
+
+        let err = pusher.join().unwrap().unwrap_err();
+        assert_eq!(err.errno(), EPIPE);
+    }
+
+    #[test]
+    fn cancel_token_unblocks_a_parked_push() {
+        let channel = Channel::<i32>::new(1).unwrap();
+        channel.push(0).unwrap(); // Fill the channel so the next push blocks.
+        let cancel_token = channel.cancel_token();
+        let channel = Arc::new(channel);
+
+        let pusher = {
+            let channel = channel.clone();
+            thread::spawn(move || channel.push(1))
+        };
+
+        thread::sleep(Duration::from_millis(50));
+        cancel_token.cancel();
+
+        let err = pusher.join().unwrap().unwrap_err();
+        assert_eq!(err.errno(), ECANCELED);
+    }
+
+    #[test]
+    fn select_reports_endpoints_as_they_become_ready() {
+        let channel_a = Channel::<i32>::new(1).unwrap();
+        let channel_b = Channel::<i32>::new(1).unwrap();
+        let (producer_a, consumer_a) = channel_a.split();
+        let (producer_b, consumer_b) = channel_b.split();
+
+        let select = Select::new(vec![
+            (&consumer_a, IoEvents::IN),
+            (&consumer_b, IoEvents::IN),
+        ]);
+
+        // Neither side has anything to read yet.
+        assert!(select.ready(Some(Duration::from_millis(10))).is_err());
+
+        producer_b.push(7).unwrap();
+        assert_eq!(select.ready(None).unwrap(), vec![1]);
+
+        consumer_b.pop().unwrap();
+        producer_a.push(9).unwrap();
+        assert_eq!(select.ready(None).unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn send_and_recv_futures_complete_when_ready() {
+        let channel = Channel::<i32>::new(1).unwrap();
+        let (producer, consumer) = channel.split();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut push_future = producer.send(7);
+        match Pin::new(&mut push_future).poll(&mut cx) {
+            Poll::Ready(Ok(())) => {}
+            other => panic!("expected Ready(Ok(())), got {:?}", other.is_ready()),
+        }
+
+        let mut pop_future = consumer.recv();
+        match Pin::new(&mut pop_future).poll(&mut cx) {
+            Poll::Ready(Ok(Some(7))) => {}
+            other => panic!("expected Ready(Ok(Some(7))), got {:?}", other.is_ready()),
+        }
+    }
+
+    #[test]
+    fn send_slice_and_recv_slice_futures_complete_when_ready() {
+        let channel = Channel::<i32>::new(4).unwrap();
+        let (producer, consumer) = channel.split();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let items = [1, 2, 3];
+        let mut push_future = producer.send_slice(&items);
+        match Pin::new(&mut push_future).poll(&mut cx) {
+            Poll::Ready(Ok(3)) => {}
+            other => panic!("expected Ready(Ok(3)), got {:?}", other.is_ready()),
+        }
+
+        let mut buf = [0; 3];
+        let mut pop_future = consumer.recv_slice(&mut buf);
+        match Pin::new(&mut pop_future).poll(&mut cx) {
+            Poll::Ready(Ok(3)) => {}
+            other => panic!("expected Ready(Ok(3)), got {:?}", other.is_ready()),
+        }
+        drop(pop_future);
+        assert_eq!(buf, [1, 2, 3]);
+    }
+
+    #[test]
+    fn pop_future_dropped_while_pending_clears_receiver_waiting() {
+        // A zero-capacity (rendezvous) channel so that a staged-but-unpopped
+        // item is observable: if `receiver_waiting` leaks `true` after the
+        // `PopFuture` below is dropped, a later non-blocking push will
+        // wrongly believe a consumer is ready and stage an item nobody will
+        // ever pick up, instead of returning `EAGAIN`.
+        let channel = Channel::<i32>::new(0).unwrap();
+        let (producer, consumer) = channel.split();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut recv_future = consumer.recv();
+        match Pin::new(&mut recv_future).poll(&mut cx) {
+            Poll::Pending => {}
+            other => panic!("expected Pending, got {:?}", other.is_ready()),
+        }
+        drop(recv_future);
+
+        producer.set_nonblocking(true);
+        let err = producer.push(1).unwrap_err();
+        assert_eq!(err.errno(), EAGAIN);
+    }
+}